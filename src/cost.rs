@@ -0,0 +1,143 @@
+//! Estimates the `k` and column budget a regex's compiled-DFA circuit will need, mirroring
+//! the accounting `halo2_proofs::dev::cost` performs for a constraint system, but computed
+//! directly from the compiled DFA so callers can size parameters before building a circuit.
+
+use halo2_proofs::pasta::Fp;
+use halo2_proofs::plonk::{ConstraintSystem, Error};
+
+use crate::dfa;
+use crate::{RegexCheckConfig, RegexCheckConfigParams};
+
+/// Fixed shape of the DFA-matching circuit built by `RegexCheckConfig`: 5 advice columns
+/// (`cur_state`, `input_byte`, `next_state`, `is_active`, `commitment_acc`), 4 fixed columns
+/// (the 3 transition table columns plus the acceptance table), 1 instance column (the public
+/// commitment), 3 selectors (`q_first`, `q_commit`, `q_last`), and 2 lookup arguments. These are
+/// constants because every regex compiles to the same circuit shape; only the row counts
+/// coming out of the DFA vary with the regex.
+const NUM_ADVICE_COLUMNS: usize = 5;
+const NUM_FIXED_COLUMNS: usize = 4;
+const NUM_INSTANCE_COLUMNS: usize = 1;
+const NUM_SELECTORS: usize = 3;
+const NUM_LOOKUPS: usize = 2;
+/// Both `"dfa row link"` (`is_active(i) * is_active(i+1) * (next_state(i) - cur_state(i+1))`)
+/// and the commitment recurrence / base-case gates reach degree 3 once their selector is folded
+/// in; none of these grow with the regex's character classes.
+const MAX_GATE_DEGREE: usize = 3;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CircuitCost {
+    pub min_k: u32,
+    pub num_advice_columns: usize,
+    pub num_fixed_columns: usize,
+    pub num_instance_columns: usize,
+    pub num_selectors: usize,
+    pub num_lookups: usize,
+    pub max_gate_degree: usize,
+    /// Rows actually used: `max(max_input_len, transition table rows, acceptance table rows)`.
+    pub used_rows: usize,
+    /// Rows halo2 itself reserves on top of `used_rows` (blinding factors and the like), read
+    /// back from a real `ConstraintSystem` rather than guessed ahead of time.
+    pub overhead_rows: usize,
+}
+
+impl CircuitCost {
+    /// Returns `Err` if `k` does not provide enough rows for this estimate, so an under-sized
+    /// `k` fails loudly here — via the same `Error` type every other fallible function in this
+    /// crate's public API already uses — instead of as a process panic reached through code
+    /// typed `Result`, or an opaque failure surfacing later out of keygen/proving/verifying.
+    pub fn assert_k_is_sufficient(&self, k: u32) -> Result<(), Error> {
+        if k >= self.min_k {
+            Ok(())
+        } else {
+            Err(Error::NotEnoughRowsAvailable { current_k: k })
+        }
+    }
+}
+
+/// Builds the real `ConstraintSystem` for `regex`/`max_input_len` purely to read back
+/// `minimum_rows()` — the blinding factors and other rows halo2 itself reserves, which grow
+/// with the number of lookup arguments a circuit has. Using the real constraint system here
+/// (rather than a guessed constant) keeps this number correct as the circuit's shape evolves,
+/// the same way `halo2_proofs::dev::cost` reads it back from a constraint system instead of
+/// assuming a fixed count.
+fn overhead_rows_for(regex: &str, max_input_len: usize) -> usize {
+    let params = RegexCheckConfigParams {
+        regex: regex.to_string(),
+        max_input_len,
+    };
+    let mut cs = ConstraintSystem::<Fp>::default();
+    RegexCheckConfig::configure_with_params(&mut cs, &params);
+    cs.minimum_rows()
+}
+
+/// Estimates the cost of the circuit `RegexCheckConfig::configure_with_params` would build for
+/// `regex` with the given `max_input_len`, by walking the compiled DFA rather than requiring a
+/// `ConstraintSystem` to already exist.
+pub fn estimate_cost(regex: &str, max_input_len: usize) -> CircuitCost {
+    let compiled = dfa::compile(regex);
+
+    // Mirrors `RegexCheckConfig::configure_with_params`'s table construction: one row per
+    // transition plus a zero padding row, and the accept states plus a zero padding row. `0`
+    // is never a real (encoded) DFA state id, so the padding row is unconditional.
+    let transition_table_rows = compiled.transitions.len() + 1;
+    let accept_table_rows = compiled.accept.len() + 1;
+
+    let used_rows = max_input_len
+        .max(transition_table_rows)
+        .max(accept_table_rows)
+        .max(1);
+    let overhead_rows = overhead_rows_for(regex, max_input_len);
+    let min_rows = used_rows + overhead_rows;
+
+    let mut min_k = 1;
+    while (1usize << min_k) < min_rows {
+        min_k += 1;
+    }
+
+    CircuitCost {
+        min_k,
+        num_advice_columns: NUM_ADVICE_COLUMNS,
+        num_fixed_columns: NUM_FIXED_COLUMNS,
+        num_instance_columns: NUM_INSTANCE_COLUMNS,
+        num_selectors: NUM_SELECTORS,
+        num_lookups: NUM_LOOKUPS,
+        max_gate_degree: MAX_GATE_DEGREE,
+        used_rows,
+        overhead_rows,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wider_character_classes_cost_more_rows_but_the_same_gate_degree() {
+        let narrow = estimate_cost("[a-z]+", 8);
+        let wide = estimate_cost("[a-zA-Z0-9]+", 8);
+
+        assert!(wide.used_rows > narrow.used_rows);
+        assert_eq!(wide.max_gate_degree, narrow.max_gate_degree);
+    }
+
+    #[test]
+    fn min_k_covers_the_requested_input_length() {
+        let cost = estimate_cost("[a-z]+", 1000);
+        assert!((1usize << cost.min_k) > 1000);
+    }
+
+    #[test]
+    fn assert_k_is_sufficient_errors_when_k_is_too_small() {
+        let cost = estimate_cost("[a-z]+", 1000);
+        assert!(matches!(
+            cost.assert_k_is_sufficient(1),
+            Err(Error::NotEnoughRowsAvailable { current_k: 1 })
+        ));
+    }
+
+    #[test]
+    fn assert_k_is_sufficient_ok_when_k_is_large_enough() {
+        let cost = estimate_cost("[a-z]+", 8);
+        assert!(cost.assert_k_is_sufficient(cost.min_k).is_ok());
+    }
+}
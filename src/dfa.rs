@@ -0,0 +1,406 @@
+//! Compiles a (small) regular-expression syntax into a deterministic finite automaton: an
+//! integer state space, a start state, a set of accepting states, and a transition function
+//! `(state, byte) -> state`. Supports literals, `[...]` character classes (with `a-z` ranges),
+//! concatenation, alternation `|`, grouping `(...)`, and the quantifiers `*`, `+`, `?` and
+//! `{m}` / `{m,}` / `{m,n}`.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+#[derive(Debug, Clone)]
+enum Node {
+    Lit(u8),
+    Class(Vec<u8>),
+    Concat(Vec<Node>),
+    Alt(Vec<Node>),
+    Repeat(Box<Node>, usize, Option<usize>),
+}
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn bump(&mut self) -> Option<u8> {
+        let ch = self.peek();
+        if ch.is_some() {
+            self.pos += 1;
+        }
+        ch
+    }
+
+    fn parse_alt(&mut self) -> Node {
+        let mut branches = vec![self.parse_concat()];
+        while self.peek() == Some(b'|') {
+            self.bump();
+            branches.push(self.parse_concat());
+        }
+        if branches.len() == 1 {
+            branches.pop().unwrap()
+        } else {
+            Node::Alt(branches)
+        }
+    }
+
+    fn parse_concat(&mut self) -> Node {
+        let mut nodes = vec![];
+        while let Some(ch) = self.peek() {
+            if ch == b'|' || ch == b')' {
+                break;
+            }
+            nodes.push(self.parse_repeat());
+        }
+        if nodes.len() == 1 {
+            nodes.pop().unwrap()
+        } else {
+            Node::Concat(nodes)
+        }
+    }
+
+    fn parse_repeat(&mut self) -> Node {
+        let atom = self.parse_atom();
+        match self.peek() {
+            Some(b'*') => {
+                self.bump();
+                Node::Repeat(Box::new(atom), 0, None)
+            }
+            Some(b'+') => {
+                self.bump();
+                Node::Repeat(Box::new(atom), 1, None)
+            }
+            Some(b'?') => {
+                self.bump();
+                Node::Repeat(Box::new(atom), 0, Some(1))
+            }
+            Some(b'{') => match self.try_parse_bounds() {
+                Some((min, max, len)) => {
+                    self.pos += len;
+                    Node::Repeat(Box::new(atom), min, max)
+                }
+                None => atom,
+            },
+            _ => atom,
+        }
+    }
+
+    /// Looks ahead (without consuming) for a `{m}` / `{m,}` / `{m,n}` bound starting at the
+    /// current `{`. A `{` that isn't followed by a well-formed bound is just a literal byte,
+    /// matching this repo's JSON regexes which use bare `{`/`}` for object delimiters.
+    fn try_parse_bounds(&self) -> Option<(usize, Option<usize>, usize)> {
+        let mut i = self.pos + 1;
+        let min_start = i;
+        while i < self.bytes.len() && self.bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        if i == min_start {
+            return None;
+        }
+        let min: usize = std::str::from_utf8(&self.bytes[min_start..i]).ok()?.parse().ok()?;
+
+        if self.bytes.get(i) == Some(&b'}') {
+            return Some((min, Some(min), i + 1 - self.pos));
+        }
+        if self.bytes.get(i) == Some(&b',') {
+            i += 1;
+            let max_start = i;
+            while i < self.bytes.len() && self.bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+            if self.bytes.get(i) == Some(&b'}') {
+                let max = if i == max_start {
+                    None
+                } else {
+                    Some(
+                        std::str::from_utf8(&self.bytes[max_start..i])
+                            .ok()?
+                            .parse()
+                            .ok()?,
+                    )
+                };
+                return Some((min, max, i + 1 - self.pos));
+            }
+        }
+        None
+    }
+
+    fn parse_atom(&mut self) -> Node {
+        match self.bump() {
+            Some(b'(') => {
+                let node = self.parse_alt();
+                if self.peek() == Some(b')') {
+                    self.bump();
+                }
+                node
+            }
+            Some(b'[') => {
+                let mut class = vec![];
+                while let Some(ch) = self.peek() {
+                    if ch == b']' {
+                        self.bump();
+                        break;
+                    }
+                    self.bump();
+                    if self.peek() == Some(b'-') && self.bytes.get(self.pos + 1) != Some(&b']') {
+                        self.bump();
+                        let hi = self.bump().unwrap_or(ch);
+                        for byte in ch..=hi {
+                            class.push(byte);
+                        }
+                    } else {
+                        class.push(ch);
+                    }
+                }
+                Node::Class(class)
+            }
+            Some(ch) => Node::Lit(ch),
+            None => Node::Concat(vec![]),
+        }
+    }
+}
+
+/// A non-deterministic automaton with epsilon transitions, built via Thompson's construction.
+struct NfaBuilder {
+    states: Vec<Vec<(Option<u8>, usize)>>,
+}
+
+impl NfaBuilder {
+    fn new() -> Self {
+        Self { states: vec![] }
+    }
+
+    fn new_state(&mut self) -> usize {
+        self.states.push(vec![]);
+        self.states.len() - 1
+    }
+
+    fn add_edge(&mut self, from: usize, byte: Option<u8>, to: usize) {
+        self.states[from].push((byte, to));
+    }
+
+    /// Builds the fragment for `node`, returning its (start, end) states.
+    fn build(&mut self, node: &Node) -> (usize, usize) {
+        match node {
+            Node::Lit(byte) => {
+                let start = self.new_state();
+                let end = self.new_state();
+                self.add_edge(start, Some(*byte), end);
+                (start, end)
+            }
+            Node::Class(bytes) => {
+                let start = self.new_state();
+                let end = self.new_state();
+                for byte in bytes {
+                    self.add_edge(start, Some(*byte), end);
+                }
+                (start, end)
+            }
+            Node::Concat(nodes) => {
+                if nodes.is_empty() {
+                    let state = self.new_state();
+                    return (state, state);
+                }
+                let mut nodes = nodes.iter();
+                let (start, mut prev_end) = self.build(nodes.next().unwrap());
+                for node in nodes {
+                    let (s, e) = self.build(node);
+                    self.add_edge(prev_end, None, s);
+                    prev_end = e;
+                }
+                (start, prev_end)
+            }
+            Node::Alt(branches) => {
+                let start = self.new_state();
+                let end = self.new_state();
+                for branch in branches {
+                    let (s, e) = self.build(branch);
+                    self.add_edge(start, None, s);
+                    self.add_edge(e, None, end);
+                }
+                (start, end)
+            }
+            Node::Repeat(inner, min, max) => {
+                let start = self.new_state();
+                let mut prev_end = start;
+                for _ in 0..*min {
+                    let (s, e) = self.build(inner);
+                    self.add_edge(prev_end, None, s);
+                    prev_end = e;
+                }
+                match max {
+                    None => {
+                        // Mandatory copies done; append a self-looping optional copy for `*`/`+`.
+                        let (s, e) = self.build(inner);
+                        self.add_edge(prev_end, None, s);
+                        self.add_edge(e, None, s);
+                        let end = self.new_state();
+                        self.add_edge(prev_end, None, end);
+                        self.add_edge(e, None, end);
+                        (start, end)
+                    }
+                    Some(max) => {
+                        let end = self.new_state();
+                        self.add_edge(prev_end, None, end);
+                        for _ in 0..max.saturating_sub(*min) {
+                            let (s, e) = self.build(inner);
+                            self.add_edge(prev_end, None, s);
+                            self.add_edge(e, None, end);
+                            prev_end = e;
+                        }
+                        (start, end)
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn epsilon_closure(states: &[Vec<(Option<u8>, usize)>], from: &BTreeSet<usize>) -> BTreeSet<usize> {
+    let mut closure = from.clone();
+    let mut stack: Vec<usize> = from.iter().copied().collect();
+    while let Some(state) = stack.pop() {
+        for (byte, target) in &states[state] {
+            if byte.is_none() && closure.insert(*target) {
+                stack.push(*target);
+            }
+        }
+    }
+    closure
+}
+
+#[derive(Debug, Clone)]
+pub struct Dfa {
+    pub num_states: usize,
+    pub start: usize,
+    pub accept: Vec<usize>,
+    /// Every valid `(state, byte) -> state` triple; a byte with no matching triple rejects.
+    pub transitions: Vec<(usize, u8, usize)>,
+}
+
+impl Dfa {
+    pub fn transition(&self, state: usize, byte: u8) -> Option<usize> {
+        self.transitions
+            .iter()
+            .find(|(s, b, _)| *s == state && *b == byte)
+            .map(|(_, _, next)| *next)
+    }
+}
+
+/// Compiles `regex` to a DFA via Thompson's construction followed by subset construction.
+pub fn compile(regex: &str) -> Dfa {
+    let mut parser = Parser::new(regex.as_bytes());
+    let ast = parser.parse_alt();
+
+    let mut builder = NfaBuilder::new();
+    let (nfa_start, nfa_end) = builder.build(&ast);
+
+    let start_set = epsilon_closure(&builder.states, &BTreeSet::from([nfa_start]));
+    let mut dfa_states: Vec<BTreeSet<usize>> = vec![start_set.clone()];
+    let mut index_of: HashMap<BTreeSet<usize>, usize> = HashMap::from([(start_set.clone(), 0)]);
+    let mut worklist = vec![start_set];
+    let mut transitions = vec![];
+
+    while let Some(current) = worklist.pop() {
+        let current_index = index_of[&current];
+        // `BTreeMap`, not `HashMap`: this loop both emits `transitions` in iteration order and
+        // assigns new DFA state indices the first time a closure is seen, so a nondeterministic
+        // byte order here would make the whole DFA's numbering (and `transitions`) vary from
+        // one `compile()` call to the next for the same regex.
+        let mut by_byte: BTreeMap<u8, BTreeSet<usize>> = BTreeMap::new();
+        for &state in &current {
+            for (byte, target) in &builder.states[state] {
+                if let Some(byte) = byte {
+                    by_byte.entry(*byte).or_default().insert(*target);
+                }
+            }
+        }
+        for (byte, targets) in by_byte {
+            let closure = epsilon_closure(&builder.states, &targets);
+            let next_index = *index_of.entry(closure.clone()).or_insert_with(|| {
+                dfa_states.push(closure.clone());
+                worklist.push(closure.clone());
+                dfa_states.len() - 1
+            });
+            transitions.push((current_index, byte, next_index));
+        }
+    }
+
+    let accept = dfa_states
+        .iter()
+        .enumerate()
+        .filter(|(_, set)| set.contains(&nfa_end))
+        .map(|(index, _)| index)
+        .collect();
+
+    Dfa {
+        num_states: dfa_states.len(),
+        start: 0,
+        accept,
+        transitions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(dfa: &Dfa, input: &str) -> bool {
+        let mut state = dfa.start;
+        for byte in input.as_bytes() {
+            match dfa.transition(state, *byte) {
+                Some(next) => state = next,
+                None => return false,
+            }
+        }
+        dfa.accept.contains(&state)
+    }
+
+    #[test]
+    fn matches_plus_quantifier() {
+        let dfa = compile("[a-z]+");
+        assert!(run(&dfa, "abc"));
+        assert!(!run(&dfa, ""));
+        assert!(!run(&dfa, "abc1"));
+    }
+
+    #[test]
+    fn matches_star_and_optional() {
+        let dfa = compile("ab*c?");
+        assert!(run(&dfa, "a"));
+        assert!(run(&dfa, "abbb"));
+        assert!(run(&dfa, "abc"));
+        assert!(!run(&dfa, "b"));
+    }
+
+    #[test]
+    fn matches_alternation_and_groups() {
+        let dfa = compile("(cat|dog)s?");
+        assert!(run(&dfa, "cat"));
+        assert!(run(&dfa, "dogs"));
+        assert!(!run(&dfa, "cats?"));
+        assert!(!run(&dfa, "bird"));
+    }
+
+    #[test]
+    fn matches_bounded_repetition() {
+        let dfa = compile("[a-z]{2,4}");
+        assert!(!run(&dfa, "a"));
+        assert!(run(&dfa, "ab"));
+        assert!(run(&dfa, "abcd"));
+        assert!(!run(&dfa, "abcde"));
+    }
+
+    #[test]
+    fn json_literals_survive_unquantified_braces() {
+        let dfa = compile("{\"[a-z]+\"}");
+        assert!(run(&dfa, "{\"abc\"}"));
+        assert!(!run(&dfa, "{{\"abc\"}"));
+    }
+}
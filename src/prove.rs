@@ -0,0 +1,117 @@
+//! High-level prover/verifier API built on halo2's IPA (Pasta) commitment scheme with a
+//! Blake2b transcript, so callers can get a succinct proof that a hidden string matches a
+//! public regex without re-implementing the keygen/transcript plumbing themselves.
+
+use std::marker::PhantomData;
+
+use halo2_proofs::{
+    pasta::{EqAffine, Fp},
+    plonk::{
+        self, create_proof, verify_proof, Error, ProvingKey, SingleVerifier, VerifyingKey,
+    },
+    poly::commitment::Params,
+    transcript::{Blake2bRead, Blake2bWrite, Challenge255},
+};
+use rand_core::OsRng;
+
+use crate::cost::estimate_cost;
+use crate::{commitment_of, MyRegexCircuit, RegexCheckConfigParams};
+
+/// Row budget used by this module's default `k`. Proving or verifying an input longer than
+/// this needs a circuit built directly with a larger `RegexCheckConfigParams::max_input_len`.
+const DEFAULT_MAX_INPUT_LEN: usize = 256;
+
+/// `k` such that `2^k` rows comfortably covers `DEFAULT_MAX_INPUT_LEN` rows of DFA walk plus
+/// the fixed lookup tables.
+const DEFAULT_K: u32 = 10;
+
+fn circuit_for(regex: &str, input: &str) -> MyRegexCircuit<Fp> {
+    MyRegexCircuit {
+        input: input.as_bytes().to_vec(),
+        params: RegexCheckConfigParams {
+            regex: regex.to_string(),
+            max_input_len: DEFAULT_MAX_INPUT_LEN,
+        },
+        _marker: PhantomData,
+    }
+}
+
+/// Builds the single `Instance` column's values for `commitment`: zero everywhere except the
+/// fixed final row, which the circuit's "public commitment" gate ties to `commitment_acc`
+/// (see `RegexCheckConfig` in the crate root).
+fn instance_column_for(commitment: Fp) -> Vec<Fp> {
+    let mut column = vec![commitment_of::<Fp>(b""); DEFAULT_MAX_INPUT_LEN];
+    column[DEFAULT_MAX_INPUT_LEN - 1] = commitment;
+    column
+}
+
+/// Generates the IPA commitment parameters and verifying key for `regex`.
+pub fn keygen_vk(regex: &str) -> Result<(Params<EqAffine>, VerifyingKey<EqAffine>), Error> {
+    estimate_cost(regex, DEFAULT_MAX_INPUT_LEN).assert_k_is_sufficient(DEFAULT_K)?;
+
+    let params: Params<EqAffine> = Params::new(DEFAULT_K);
+    let circuit = circuit_for(regex, "");
+    let vk = plonk::keygen_vk(&params, &circuit)?;
+    Ok((params, vk))
+}
+
+/// Generates the IPA commitment parameters and proving key for `regex`.
+pub fn keygen_pk(regex: &str) -> Result<(Params<EqAffine>, ProvingKey<EqAffine>), Error> {
+    let (params, vk) = keygen_vk(regex)?;
+    let circuit = circuit_for(regex, "");
+    let pk = plonk::keygen_pk(&params, vk, &circuit)?;
+    Ok((params, pk))
+}
+
+/// Proves that `input` matches `regex`, returning the public commitment to `input` (see
+/// `commitment_of`) alongside the serialized transcript bytes. `input` itself stays private to
+/// the prover; the caller must pass the returned commitment to [`verify`] along with the proof,
+/// since the commitment — not `input` — is what pins the proof to a specific hidden string.
+pub fn prove(regex: &str, input: &str) -> Result<(Fp, Vec<u8>), Error> {
+    let (params, pk) = keygen_pk(regex)?;
+    let circuit = circuit_for(regex, input);
+    let commitment = commitment_of::<Fp>(input.as_bytes());
+    let instance = instance_column_for(commitment);
+
+    let mut transcript = Blake2bWrite::<_, EqAffine, Challenge255<_>>::init(vec![]);
+    create_proof(&params, &pk, &[circuit], &[&[&instance[..]]], OsRng, &mut transcript)?;
+    Ok((commitment, transcript.finalize()))
+}
+
+/// Checks a proof produced by [`prove`] against `regex` and the same `commitment` [`prove`]
+/// returned. A proof that matches the regex but was produced for a different hidden string
+/// fails here even though every other gate is satisfied, since `commitment` wouldn't match the
+/// `commitment_acc` value baked into that proof.
+pub fn verify(regex: &str, commitment: Fp, proof: &[u8]) -> Result<(), Error> {
+    let (params, vk) = keygen_vk(regex)?;
+    let strategy = SingleVerifier::new(&params);
+    let instance = instance_column_for(commitment);
+    let mut transcript = Blake2bRead::<_, EqAffine, Challenge255<_>>::init(proof);
+    verify_proof(&params, &vk, strategy, &[&[&instance[..]]], &mut transcript)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proves_and_verifies_a_matching_input() {
+        let regex = "[a-z]+";
+        let (commitment, proof) = prove(regex, "abc").expect("proving a matching input should succeed");
+        verify(regex, commitment, &proof).expect("a valid proof should verify");
+    }
+
+    #[test]
+    fn rejects_an_input_that_does_not_match() {
+        let regex = "[a-z]+";
+        assert!(prove(regex, "ABC").is_err());
+    }
+
+    #[test]
+    fn rejects_a_proof_checked_against_the_wrong_commitment() {
+        let regex = "[a-z]+";
+        let (_, proof) = prove(regex, "abc").expect("proving a matching input should succeed");
+        let wrong_commitment = commitment_of::<Fp>(b"xyz");
+        assert!(verify(regex, wrong_commitment, &proof).is_err());
+    }
+}